@@ -6,7 +6,7 @@ use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
 pub use regex::Error as RegexError;
-use regex::{RegexAtom, RegexJoin, RegexNode, RegexOr, RegexRepeat};
+use regex::{RegexAtom, RegexClass, RegexJoin, RegexNode, RegexOr, RegexQuantified, RegexRepeat};
 
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
@@ -20,6 +20,63 @@ pub fn compile_regex(input: &str) -> Result<StateMachines, String> {
     Ok(StateMachines { nfa, dfa })
 }
 
+/// Compiles `pattern` and runs it against `input` in one call, reporting
+/// whether the leftmost-longest match was found, where it ended, and the
+/// sequence of DFA states visited so a UI can animate the run.
+#[wasm_bindgen]
+pub fn match_regex(pattern: &str, input: &str) -> Result<MatchResult, String> {
+    let nfa = Nfa::from_regex(pattern).map_err(|e| e.to_string())?;
+    let dfa = Dfa::from_nfa(&nfa);
+
+    let mut state = 0;
+    let mut path = vec![state];
+    let mut end = dfa.states[state].accepts.then_some(0);
+
+    for (i, c) in input.char_indices() {
+        let Some(next) = dfa.step(state, c) else {
+            break;
+        };
+        state = next;
+        path.push(state);
+        if dfa.states[state].accepts {
+            end = Some(i + c.len_utf8());
+        }
+    }
+
+    Ok(MatchResult {
+        matched: end.is_some(),
+        end: end.unwrap_or(0),
+        path,
+    })
+}
+
+/// Compiles `input` and renders both automata as Graphviz DOT source.
+#[wasm_bindgen]
+pub fn compile_regex_dot(input: &str) -> Result<RegexDot, String> {
+    let nfa = Nfa::from_regex(input).map_err(|e| e.to_string())?;
+    let dfa = Dfa::from_nfa(&nfa);
+
+    Ok(RegexDot {
+        nfa: nfa.to_dot(),
+        dfa: dfa.to_dot(),
+    })
+}
+
+#[derive(Debug, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct RegexDot {
+    pub nfa: String,
+    pub dfa: String,
+}
+
+#[derive(Debug, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+pub struct MatchResult {
+    pub matched: bool,
+    pub end: usize,
+    pub path: Vec<usize>,
+}
+
 #[derive(Debug, Serialize, Tsify)]
 #[tsify(into_wasm_abi)]
 pub struct StateMachines {
@@ -27,6 +84,154 @@ pub struct StateMachines {
     dfa: Nfa,
 }
 
+/// An inclusive range of `char`s, used as the key for a transition so that
+/// whole classes like `[a-z]` don't need to be enumerated one char at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Tsify)]
+pub struct CharRange {
+    pub start: char,
+    pub end: char,
+}
+
+impl CharRange {
+    pub fn single(c: char) -> Self {
+        Self { start: c, end: c }
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        self.start <= c && c <= self.end
+    }
+
+    pub fn contains_range(&self, other: &CharRange) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+}
+
+/// Renders a range as a Graphviz edge label, collapsing single-char ranges to
+/// just the char and wider ones to `start-end`.
+fn dot_label(range: &CharRange) -> String {
+    if range.start == range.end {
+        range.start.escape_default().to_string()
+    } else {
+        format!(
+            "{}-{}",
+            range.start.escape_default(),
+            range.end.escape_default()
+        )
+    }
+}
+
+/// The char immediately after `c`, skipping the surrogate gap, or `None` past `char::MAX`.
+fn next_char(c: char) -> Option<char> {
+    let n = c as u32;
+    if n == char::MAX as u32 {
+        return None;
+    }
+    if n + 1 == 0xD800 {
+        return Some('\u{E000}');
+    }
+    char::from_u32(n + 1)
+}
+
+/// The char immediately before `c`, skipping the surrogate gap, or `None` before `'\0'`.
+fn prev_char(c: char) -> Option<char> {
+    let n = c as u32;
+    if n == 0 {
+        return None;
+    }
+    if n - 1 == 0xDFFF {
+        return Some('\u{D7FF}');
+    }
+    char::from_u32(n - 1)
+}
+
+/// Merges overlapping/adjacent ranges into a sorted, disjoint set.
+fn merge_ranges(mut ranges: Vec<CharRange>) -> Vec<CharRange> {
+    ranges.sort_by_key(|r| (r.start, r.end));
+
+    let mut merged: Vec<CharRange> = Vec::new();
+    for r in ranges {
+        let adjacent = match merged.last() {
+            Some(last) => match next_char(last.end) {
+                Some(next) => r.start <= next,
+                None => true,
+            },
+            None => false,
+        };
+
+        if adjacent {
+            let last = merged.last_mut().unwrap();
+            if r.end > last.end {
+                last.end = r.end;
+            }
+        } else {
+            merged.push(r);
+        }
+    }
+    merged
+}
+
+/// Complements a sorted, disjoint set of ranges over the full `char` domain.
+fn negate_ranges(ranges: &[CharRange]) -> Vec<CharRange> {
+    let mut result = Vec::new();
+    let mut cursor = '\u{0}';
+
+    for r in ranges {
+        if cursor < r.start {
+            if let Some(end) = prev_char(r.start) {
+                result.push(CharRange { start: cursor, end });
+            }
+        }
+        match next_char(r.end) {
+            Some(next) => cursor = next,
+            None => return result,
+        }
+    }
+
+    result.push(CharRange {
+        start: cursor,
+        end: char::MAX,
+    });
+    result
+}
+
+/// Resolves a (possibly negated) character class into its disjoint covering ranges.
+fn class_ranges(pattern: &RegexClass) -> Vec<CharRange> {
+    let merged = merge_ranges(pattern.ranges.clone());
+    if pattern.negated {
+        negate_ranges(&merged)
+    } else {
+        merged
+    }
+}
+
+/// Splits `points` (sorted boundary chars) into the contiguous ranges between them,
+/// with the final range open-ended at `char::MAX`.
+fn partition_cells(points: &[char]) -> Vec<CharRange> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = match points.get(i + 1) {
+                Some(&next) => prev_char(next).unwrap_or(start),
+                None => char::MAX,
+            };
+            CharRange { start, end }
+        })
+        .collect()
+}
+
+/// Collects the boundary points (range starts and one-past-the-end) of `ranges`.
+fn partition_points<'a>(ranges: impl IntoIterator<Item = &'a CharRange>) -> Vec<char> {
+    let mut boundaries = BTreeSet::new();
+    for range in ranges {
+        boundaries.insert(range.start);
+        if let Some(next) = next_char(range.end) {
+            boundaries.insert(next);
+        }
+    }
+    boundaries.into_iter().collect()
+}
+
 #[derive(Debug, Serialize, Tsify)]
 pub struct Nfa {
     pub states: Vec<NfaState>,
@@ -34,7 +239,7 @@ pub struct Nfa {
 
 #[derive(Debug, Clone, Serialize, Tsify)]
 pub struct NfaState {
-    pub branches: BTreeMap<char, Vec<usize>>,
+    pub branches: BTreeMap<CharRange, Vec<usize>>,
     pub epsilon_transitions: Vec<usize>,
     pub accepts: bool,
 }
@@ -62,7 +267,9 @@ impl Nfa {
     fn insert(&mut self, state: usize, pattern: &RegexNode) -> usize {
         match pattern {
             RegexNode::Atom(e) => self.insert_atom(state, e),
+            RegexNode::Class(e) => self.insert_class(state, e),
             RegexNode::Repeat(e) => self.insert_repeat(state, e),
+            RegexNode::Quantified(e) => self.insert_quantified(state, e),
             RegexNode::Or(e) => self.insert_or(state, e),
             RegexNode::Join(e) => self.insert_join(state, e),
         }
@@ -71,13 +278,25 @@ impl Nfa {
     fn insert_atom(&mut self, mut state: usize, pattern: &RegexAtom) -> usize {
         for c in pattern.literal.chars() {
             let s = self.alloc_state();
-            let edges = self.states[state].branches.entry(c).or_default();
+            let edges = self.states[state]
+                .branches
+                .entry(CharRange::single(c))
+                .or_default();
             edges.push(s);
             state = s;
         }
         state
     }
 
+    fn insert_class(&mut self, state: usize, pattern: &RegexClass) -> usize {
+        let s = self.alloc_state();
+        for range in class_ranges(pattern) {
+            let edges = self.states[state].branches.entry(range).or_default();
+            edges.push(s);
+        }
+        s
+    }
+
     fn insert_repeat(&mut self, state: usize, pattern: &RegexRepeat) -> usize {
         let loop_start = self.alloc_state();
         self.states[state].epsilon_transitions.push(loop_start);
@@ -86,6 +305,34 @@ impl Nfa {
         loop_start
     }
 
+    fn insert_quantified(&mut self, mut state: usize, pattern: &RegexQuantified) -> usize {
+        for _ in 0..pattern.min {
+            state = self.insert(state, &pattern.pattern);
+        }
+
+        match pattern.max {
+            None => {
+                let loop_start = self.alloc_state();
+                self.states[state].epsilon_transitions.push(loop_start);
+                let s = self.insert(loop_start, &pattern.pattern);
+                self.states[s].epsilon_transitions.push(loop_start);
+                loop_start
+            }
+            Some(max) => {
+                let out = self.alloc_state();
+                self.states[state].epsilon_transitions.push(out);
+
+                let mut s = state;
+                for _ in pattern.min..max {
+                    s = self.insert(s, &pattern.pattern);
+                    self.states[s].epsilon_transitions.push(out);
+                }
+
+                out
+            }
+        }
+    }
+
     fn insert_or(&mut self, state: usize, pattern: &RegexOr) -> usize {
         let s0 = self.insert(state, &pattern.left);
         let s1 = self.insert(state, &pattern.right);
@@ -102,6 +349,49 @@ impl Nfa {
         let state = self.insert(state, &pattern.left);
         self.insert(state, &pattern.right)
     }
+
+    pub fn is_match(&self, input: &str) -> bool {
+        let mut states = epsilon_closure(self, [0]);
+
+        for c in input.chars() {
+            if states.is_empty() {
+                return false;
+            }
+
+            states = match transitions(self, states.iter().copied())
+                .into_iter()
+                .find(|(range, _)| range.contains(c))
+            {
+                Some((_, next)) => next,
+                None => return false,
+            };
+        }
+
+        states.iter().any(|&s| self.states[s].accepts)
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph NFA {\n    rankdir=LR;\n    __start [shape=point];\n    __start -> 0;\n");
+
+        for (i, s) in self.states.iter().enumerate() {
+            let shape = if s.accepts { "doublecircle" } else { "circle" };
+            dot.push_str(&format!("    {i} [shape={shape}];\n"));
+        }
+
+        for (i, s) in self.states.iter().enumerate() {
+            for (range, targets) in &s.branches {
+                for &t in targets {
+                    dot.push_str(&format!("    {i} -> {t} [label=\"{}\"];\n", dot_label(range)));
+                }
+            }
+            for &t in &s.epsilon_transitions {
+                dot.push_str(&format!("    {i} -> {t} [label=\"\u{3b5}\"];\n"));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
 }
 
 impl From<Dfa> for Nfa {
@@ -127,7 +417,7 @@ pub struct Dfa {
 
 #[derive(Debug, Clone, Serialize, Tsify)]
 pub struct DfaState {
-    pub branches: BTreeMap<char, usize>,
+    pub branches: BTreeMap<CharRange, usize>,
     pub accepts: bool,
 }
 
@@ -152,7 +442,7 @@ impl Dfa {
             let mut branches = BTreeMap::new();
             let transisions = transitions(state_machine, s.iter().copied());
 
-            for (c, s) in transisions {
+            for (range, s) in transisions {
                 let id = match state_map.get(&s) {
                     Some(s) => *s,
                     None => {
@@ -168,7 +458,7 @@ impl Dfa {
                     }
                 };
 
-                branches.insert(c, id);
+                branches.insert(range, id);
             }
 
             states[state_id].branches = branches;
@@ -177,10 +467,198 @@ impl Dfa {
         Self { states }
     }
 
+    fn step(&self, state: usize, c: char) -> Option<usize> {
+        self.states[state]
+            .branches
+            .iter()
+            .find(|(range, _)| range.contains(c))
+            .map(|(_, &target)| target)
+    }
+
+    pub fn is_match(&self, input: &str) -> bool {
+        let mut state = 0;
+        for c in input.chars() {
+            match self.step(state, c) {
+                Some(next) => state = next,
+                None => return false,
+            }
+        }
+        self.states[state].accepts
+    }
+
+    /// Longest accepting prefix of `input`, as a byte offset, or `None` if no
+    /// prefix (including the empty one) is accepted.
+    pub fn find(&self, input: &str) -> Option<usize> {
+        let mut state = 0;
+        let mut best = self.states[state].accepts.then_some(0);
+
+        for (i, c) in input.char_indices() {
+            let Some(next) = self.step(state, c) else {
+                break;
+            };
+            state = next;
+            if self.states[state].accepts {
+                best = Some(i + c.len_utf8());
+            }
+        }
+
+        best
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph DFA {\n    rankdir=LR;\n    __start [shape=point];\n    __start -> 0;\n");
+
+        for (i, s) in self.states.iter().enumerate() {
+            let shape = if s.accepts { "doublecircle" } else { "circle" };
+            dot.push_str(&format!("    {i} [shape={shape}];\n"));
+        }
+
+        for (i, s) in self.states.iter().enumerate() {
+            for (range, &t) in &s.branches {
+                dot.push_str(&format!("    {i} -> {t} [label=\"{}\"];\n", dot_label(range)));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
     pub fn optimize(&self) -> Self {
-        let _reachables = reachable_states(self, 0);
+        let reachables = reachable_states(self, 0);
+
+        let old_ids: Vec<usize> = reachables.into_iter().collect();
+        let n = old_ids.len();
+        let sink = n;
+        let total = n + 1;
+
+        let renumber: BTreeMap<usize, usize> = old_ids
+            .iter()
+            .enumerate()
+            .map(|(new_id, &old_id)| (old_id, new_id))
+            .collect();
+
+        let all_ranges: Vec<CharRange> = old_ids
+            .iter()
+            .flat_map(|&s| self.states[s].branches.keys().copied())
+            .collect();
+        let points = partition_points(all_ranges.iter());
+        let alphabet = partition_cells(&points);
+
+        let mut trans: Vec<BTreeMap<CharRange, usize>> = vec![BTreeMap::new(); total];
+        for (new_id, &old_id) in old_ids.iter().enumerate() {
+            for &cell in &alphabet {
+                if let Some((_, &target)) = self.states[old_id]
+                    .branches
+                    .iter()
+                    .find(|(range, _)| range.contains_range(&cell))
+                {
+                    trans[new_id].insert(cell, *renumber.get(&target).unwrap());
+                }
+            }
+        }
+        for &cell in &alphabet {
+            trans[sink].insert(cell, sink);
+        }
+
+        let accepting: BTreeSet<usize> = (0..n)
+            .filter(|&s| self.states[old_ids[s]].accepts)
+            .collect();
+        let non_accepting: BTreeSet<usize> = (0..total).filter(|s| !accepting.contains(s)).collect();
+
+        let mut partition: Vec<BTreeSet<usize>> = [accepting, non_accepting]
+            .into_iter()
+            .filter(|p| !p.is_empty())
+            .collect();
+
+        let mut worklist: VecDeque<BTreeSet<usize>> = VecDeque::new();
+        if partition.len() == 2 {
+            let smaller = if partition[0].len() <= partition[1].len() {
+                partition[0].clone()
+            } else {
+                partition[1].clone()
+            };
+            worklist.push_back(smaller);
+        }
+
+        while let Some(a) = worklist.pop_front() {
+            for &cell in &alphabet {
+                let x: BTreeSet<usize> = (0..total)
+                    .filter(|s| a.contains(trans[*s].get(&cell).unwrap_or(&sink)))
+                    .collect();
+
+                if x.is_empty() {
+                    continue;
+                }
+
+                let mut next_partition = Vec::with_capacity(partition.len());
+                for y in &partition {
+                    let intersection: BTreeSet<usize> = y.intersection(&x).copied().collect();
+                    let difference: BTreeSet<usize> = y.difference(&x).copied().collect();
+
+                    if intersection.is_empty() || difference.is_empty() {
+                        next_partition.push(y.clone());
+                        continue;
+                    }
+
+                    next_partition.push(intersection.clone());
+                    next_partition.push(difference.clone());
+
+                    if let Some(pos) = worklist.iter().position(|w| w == y) {
+                        worklist.remove(pos);
+                        worklist.push_back(intersection);
+                        worklist.push_back(difference);
+                    } else if intersection.len() <= difference.len() {
+                        worklist.push_back(intersection);
+                    } else {
+                        worklist.push_back(difference);
+                    }
+                }
+                partition = next_partition;
+            }
+        }
+
+        let initial_block = partition
+            .iter()
+            .position(|p| p.contains(&renumber[&0]))
+            .unwrap();
+        let sink_block = partition.iter().position(|p| p.contains(&sink));
+
+        let mut block_order: Vec<usize> = (0..partition.len())
+            .filter(|&b| Some(b) != sink_block)
+            .collect();
+        block_order.sort_by_key(|&b| if b == initial_block { 0 } else { 1 });
 
-        todo!()
+        let block_id: BTreeMap<usize, usize> = block_order
+            .iter()
+            .enumerate()
+            .map(|(new_id, &block)| (block, new_id))
+            .collect();
+
+        let states = block_order
+            .iter()
+            .map(|&block| {
+                let members = &partition[block];
+                let representative = *members.iter().next().unwrap();
+                let accepts = members
+                    .iter()
+                    .any(|&s| s < n && self.states[old_ids[s]].accepts);
+
+                let mut branches = BTreeMap::new();
+                for &cell in &alphabet {
+                    let Some(&target) = trans[representative].get(&cell) else {
+                        continue;
+                    };
+                    let target_block = partition.iter().position(|p| p.contains(&target)).unwrap();
+                    if let Some(&id) = block_id.get(&target_block) {
+                        branches.insert(cell, id);
+                    }
+                }
+
+                DfaState { branches, accepts }
+            })
+            .collect();
+
+        Self { states }
     }
 }
 
@@ -204,20 +682,32 @@ fn reachable_states(dfa: &Dfa, state: usize) -> BTreeSet<usize> {
 fn transitions(
     state_machine: &Nfa,
     states: impl IntoIterator<Item = usize>,
-) -> BTreeMap<char, BTreeSet<usize>> {
-    let mut transisions = BTreeMap::new();
-
-    let pairs = states
+) -> BTreeMap<CharRange, BTreeSet<usize>> {
+    let edges: Vec<(CharRange, usize)> = states
         .into_iter()
-        .flat_map(|s| state_machine.states[s].branches.iter());
+        .flat_map(|s| {
+            state_machine.states[s]
+                .branches
+                .iter()
+                .flat_map(|(&range, targets)| targets.iter().map(move |&t| (range, t)))
+        })
+        .collect();
 
-    for (&c, s) in pairs {
-        let v: &mut BTreeSet<usize> = transisions.entry(c).or_default();
-        v.extend(s);
-    }
+    let points = partition_points(edges.iter().map(|(range, _)| range));
+
+    let mut transisions = BTreeMap::new();
+    for cell in partition_cells(&points) {
+        let targets: BTreeSet<usize> = edges
+            .iter()
+            .filter(|(range, _)| range.contains_range(&cell))
+            .map(|(_, t)| *t)
+            .collect();
+
+        if targets.is_empty() {
+            continue;
+        }
 
-    for s in transisions.values_mut() {
-        *s = epsilon_closure(state_machine, s.iter().copied());
+        transisions.insert(cell, epsilon_closure(state_machine, targets));
     }
 
     transisions
@@ -240,3 +730,137 @@ fn epsilon_closure(
 
     reachable
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn minimized_state_count(pattern: &str) -> usize {
+        let nfa = Nfa::from_regex(pattern).unwrap();
+        let dfa = Dfa::from_nfa(&nfa);
+        dfa.optimize().states.len()
+    }
+
+    #[test]
+    fn test_optimize_must_minimize_equivalent_regexes_to_the_same_state_count() {
+        assert_eq!(minimized_state_count("(a|a)*"), minimized_state_count("a*"));
+    }
+
+    #[test]
+    fn test_optimize_must_keep_accepting_dead_and_initial_states_distinct() {
+        let nfa = Nfa::from_regex("ab").unwrap();
+        let dfa = Dfa::from_nfa(&nfa).optimize();
+
+        assert_eq!(dfa.states.len(), 3);
+        assert!(!dfa.states[0].accepts);
+        assert!(dfa.states[0]
+            .branches
+            .contains_key(&CharRange::single('a')));
+        assert_eq!(dfa.states.iter().filter(|s| s.accepts).count(), 1);
+        assert!(dfa.is_match("ab"));
+    }
+
+    #[test]
+    fn test_optimize_must_minimize_quantifiers_like_their_expansions() {
+        assert_eq!(minimized_state_count("a+"), minimized_state_count("a(a)*"));
+        assert_eq!(minimized_state_count("a{3}"), minimized_state_count("aaa"));
+        assert_eq!(minimized_state_count("a{2,}"), minimized_state_count("aa(a)*"));
+        assert_eq!(minimized_state_count("a?"), minimized_state_count("(a|a)?"));
+    }
+
+    #[test]
+    fn test_dfa_from_nfa_must_split_overlapping_class_ranges() {
+        // `[a-m]|[h-z]` should yield disjoint partitions a-g, h-m, n-z.
+        let nfa = Nfa::from_regex("[a-m]|[h-z]").unwrap();
+        let dfa = Dfa::from_nfa(&nfa);
+
+        let mut ranges: Vec<CharRange> = dfa.states[0].branches.keys().copied().collect();
+        ranges.sort();
+
+        assert_eq!(
+            ranges,
+            vec![
+                CharRange {
+                    start: 'a',
+                    end: 'g'
+                },
+                CharRange {
+                    start: 'h',
+                    end: 'm'
+                },
+                CharRange {
+                    start: 'n',
+                    end: 'z'
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_next_char_and_prev_char_must_jump_across_the_surrogate_gap() {
+        assert_eq!(next_char('\u{D7FF}'), Some('\u{E000}'));
+        assert_eq!(prev_char('\u{E000}'), Some('\u{D7FF}'));
+    }
+
+    #[test]
+    fn test_negate_ranges_must_keep_chars_just_below_the_surrogate_gap() {
+        let negated = negate_ranges(&[CharRange::single('\u{E000}')]);
+
+        assert!(negated
+            .iter()
+            .any(|r| r.contains_range(&CharRange::single('\u{D7FF}'))));
+    }
+
+    fn compile(pattern: &str) -> Dfa {
+        let nfa = Nfa::from_regex(pattern).unwrap();
+        Dfa::from_nfa(&nfa)
+    }
+
+    #[test]
+    fn test_dfa_is_match_must_accept_and_reject_input() {
+        let dfa = compile("a(b|c)*");
+
+        assert!(dfa.is_match("a"));
+        assert!(dfa.is_match("abcbc"));
+        assert!(!dfa.is_match("b"));
+        assert!(!dfa.is_match("abcd"));
+    }
+
+    #[test]
+    fn test_nfa_is_match_must_agree_with_dfa_is_match() {
+        let nfa = Nfa::from_regex("a+b?").unwrap();
+        let dfa = Dfa::from_nfa(&nfa);
+
+        for input in ["a", "aaa", "ab", "aaab", "", "b"] {
+            assert_eq!(nfa.is_match(input), dfa.is_match(input), "input {input:?}");
+        }
+    }
+
+    #[test]
+    fn test_dfa_find_must_return_the_leftmost_longest_accepting_prefix() {
+        let dfa = compile("a(b|c)*");
+
+        assert_eq!(dfa.find("abcbcx"), Some(5));
+        assert_eq!(dfa.find("a"), Some(1));
+        assert_eq!(dfa.find("xyz"), None);
+    }
+
+    #[test]
+    fn test_dfa_to_dot_must_render_a_double_circle_for_accepting_states_and_collapse_ranges() {
+        let dfa = compile("[a-z]");
+        let dot = dfa.to_dot();
+
+        assert!(dot.starts_with("digraph DFA {"));
+        assert!(dot.contains("doublecircle"));
+        assert!(dot.contains("label=\"a-z\""));
+    }
+
+    #[test]
+    fn test_nfa_to_dot_must_label_epsilon_transitions() {
+        let nfa = Nfa::from_regex("a*").unwrap();
+        let dot = nfa.to_dot();
+
+        assert!(dot.starts_with("digraph NFA {"));
+        assert!(dot.contains("label=\"\u{3b5}\""));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::CharRange;
+
 #[derive(Debug)]
 pub struct Regex {
     pub root: RegexNode,
@@ -6,7 +8,9 @@ pub struct Regex {
 #[derive(Debug)]
 pub enum RegexNode {
     Atom(RegexAtom),
+    Class(RegexClass),
     Repeat(Box<RegexRepeat>),
+    Quantified(Box<RegexQuantified>),
     Or(Box<RegexOr>),
     Join(Box<RegexJoin>),
 }
@@ -16,12 +20,28 @@ pub struct RegexAtom {
     pub literal: String,
 }
 
+// [abc], [a-z], [^...], .
+#[derive(Debug)]
+pub struct RegexClass {
+    pub ranges: Vec<CharRange>,
+    pub negated: bool,
+}
+
 // p *
 #[derive(Debug)]
 pub struct RegexRepeat {
     pub pattern: RegexNode,
 }
 
+// p+, p?, p{n}, p{n,}, p{n,m}: `min` mandatory occurrences, then optional
+// ones up to `max` occurrences in total (unbounded if `None`).
+#[derive(Debug)]
+pub struct RegexQuantified {
+    pub pattern: RegexNode,
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
 // p0 | p1
 #[derive(Debug)]
 pub struct RegexOr {
@@ -57,7 +77,7 @@ impl std::str::FromStr for Regex {
 }
 
 fn parse<'a>(s: &'a str, terminal: Option<&Token>) -> Result<(RegexNode, &'a str), Error> {
-    let (token, rest) = match take_token(s) {
+    let (token, rest) = match take_token(s)? {
         Some(e) => e,
         None => return Err(Error::Empty),
     };
@@ -67,9 +87,24 @@ fn parse<'a>(s: &'a str, terminal: Option<&Token>) -> Result<(RegexNode, &'a str
             let p = RegexNode::Atom(RegexAtom { literal });
             (p, rest)
         }
+        Token::Shorthand(kind) => (RegexNode::Class(shorthand_class(kind)), rest),
+        Token::Dot => {
+            let p = RegexNode::Class(RegexClass {
+                ranges: vec![CharRange {
+                    start: '\u{0}',
+                    end: char::MAX,
+                }],
+                negated: false,
+            });
+            (p, rest)
+        }
+        Token::LBracket => {
+            let (class, rest) = parse_class(rest)?;
+            (RegexNode::Class(class), rest)
+        }
         Token::LParen => {
-            let (p, rest) = parse(rest, terminal)?;
-            match take_token(rest) {
+            let (p, rest) = parse(rest, None)?;
+            match take_token(rest)? {
                 Some((Token::RParen, rest)) => (p, rest),
                 Some(_) => return Err(Error::UnexpectedToken),
                 None => return Err(Error::UnexpectedEnd),
@@ -78,16 +113,37 @@ fn parse<'a>(s: &'a str, terminal: Option<&Token>) -> Result<(RegexNode, &'a str
         _ => return Err(Error::UnexpectedToken),
     };
 
-    match take_token(rest) {
+    match take_token(rest)? {
         Some((Token::Asterisk, r)) => {
             node = RegexNode::Repeat(Box::new(RegexRepeat { pattern: node }));
             rest = r;
         }
+        Some((Token::Plus, r)) => {
+            node = RegexNode::Quantified(Box::new(RegexQuantified {
+                pattern: node,
+                min: 1,
+                max: None,
+            }));
+            rest = r;
+        }
+        Some((Token::Question, r)) => {
+            node = RegexNode::Quantified(Box::new(RegexQuantified {
+                pattern: node,
+                min: 0,
+                max: Some(1),
+            }));
+            rest = r;
+        }
+        Some((Token::LBrace, r)) => {
+            let (min, max, r) = parse_quantifier_bounds(r)?;
+            node = RegexNode::Quantified(Box::new(RegexQuantified { pattern: node, min, max }));
+            rest = r;
+        }
         _ => (),
     };
 
     loop {
-        let (token, r) = match take_token(rest) {
+        let (token, r) = match take_token(rest)? {
             Some(v) => v,
             None => break,
         };
@@ -124,29 +180,48 @@ fn parse<'a>(s: &'a str, terminal: Option<&Token>) -> Result<(RegexNode, &'a str
 #[derive(Debug, PartialEq, Eq)]
 enum Token {
     Literal(String),
+    /// A `\d`, `\D`, `\w`, `\W`, `\s` or `\S` shorthand class, carrying its letter.
+    Shorthand(char),
     LParen,
     RParen,
+    LBracket,
     Asterisk,
+    Plus,
+    Question,
+    LBrace,
     VerticalBar,
+    Dot,
 }
 
-fn take_token(s: &str) -> Option<(Token, &str)> {
+fn take_token(s: &str) -> Result<Option<(Token, &str)>, Error> {
     let mut chars = s.chars();
     let Some(c) = chars.next() else {
-        return None;
+        return Ok(None);
     };
 
     let t = match c {
         '(' => (Token::LParen, chars.as_str()),
         ')' => (Token::RParen, chars.as_str()),
+        '[' => (Token::LBracket, chars.as_str()),
         '*' => (Token::Asterisk, chars.as_str()),
+        '+' => (Token::Plus, chars.as_str()),
+        '?' => (Token::Question, chars.as_str()),
+        '{' => (Token::LBrace, chars.as_str()),
         '|' => (Token::VerticalBar, chars.as_str()),
+        '.' => (Token::Dot, chars.as_str()),
+        '\\' => {
+            let Some(escaped) = chars.next() else {
+                return Err(Error::UnexpectedEnd);
+            };
+            let rest = chars.as_str();
+            match escaped {
+                'd' | 'D' | 'w' | 'W' | 's' | 'S' => (Token::Shorthand(escaped), rest),
+                _ => (Token::Literal(escaped.to_string()), rest),
+            }
+        }
         _ => {
             let len = s
-                .find(|c| match c {
-                    '(' | ')' | '*' | '|' => true,
-                    _ => false,
-                })
+                .find(['(', ')', '[', '*', '+', '?', '{', '|', '.', '\\'])
                 .unwrap_or(s.len());
 
             let (lit, rest) = s.split_at(len);
@@ -154,7 +229,122 @@ fn take_token(s: &str) -> Option<(Token, &str)> {
         }
     };
 
-    Some(t)
+    Ok(Some(t))
+}
+
+// Resolves a `\d`/`\w`/`\s` shorthand (and its uppercase negation) into the
+// character class it stands for.
+fn shorthand_class(kind: char) -> RegexClass {
+    const DIGIT: CharRange = CharRange { start: '0', end: '9' };
+    const WORD: &[CharRange] = &[
+        CharRange { start: 'a', end: 'z' },
+        CharRange { start: 'A', end: 'Z' },
+        CharRange { start: '0', end: '9' },
+        CharRange { start: '_', end: '_' },
+    ];
+    const SPACE: &[CharRange] = &[
+        CharRange { start: ' ', end: ' ' },
+        CharRange { start: '\t', end: '\r' },
+    ];
+
+    let (ranges, negated) = match kind {
+        'd' => (vec![DIGIT], false),
+        'D' => (vec![DIGIT], true),
+        'w' => (WORD.to_vec(), false),
+        'W' => (WORD.to_vec(), true),
+        's' => (SPACE.to_vec(), false),
+        'S' => (SPACE.to_vec(), true),
+        _ => unreachable!("take_token only produces Shorthand for d/D/w/W/s/S"),
+    };
+
+    RegexClass { ranges, negated }
+}
+
+// Parses the `n`, `n,`, or `n,m` body of a `{...}` counted repetition, already
+// past the opening brace. Numbers are scanned directly since digits aren't
+// otherwise part of the token grammar.
+fn parse_quantifier_bounds(s: &str) -> Result<(usize, Option<usize>, &str), Error> {
+    let (min, rest) = take_digits(s);
+    if min.is_empty() {
+        return Err(Error::UnexpectedToken);
+    }
+    let min: usize = min.parse().map_err(|_| Error::UnexpectedToken)?;
+
+    let (max, rest) = match rest.strip_prefix(',') {
+        Some(rest) => {
+            let (max, rest) = take_digits(rest);
+            if max.is_empty() {
+                (None, rest)
+            } else {
+                let max: usize = max.parse().map_err(|_| Error::UnexpectedToken)?;
+                (Some(max), rest)
+            }
+        }
+        None => (Some(min), rest),
+    };
+
+    let rest = rest.strip_prefix('}').ok_or(Error::UnexpectedEnd)?;
+
+    if max.is_some_and(|max| max < min) {
+        return Err(Error::UnexpectedToken);
+    }
+
+    Ok((min, max, rest))
+}
+
+fn take_digits(s: &str) -> (&str, &str) {
+    let len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s.split_at(len)
+}
+
+// Scans a `[...]` body directly off the raw input, bypassing `take_token`,
+// since inside a class the usual metacharacters (`(`, `)`, `*`, `|`) are literal.
+fn parse_class(s: &str) -> Result<(RegexClass, &str), Error> {
+    let negated = s.starts_with('^');
+    let mut rest = if negated { &s[1..] } else { s };
+
+    let mut ranges = Vec::new();
+    loop {
+        let mut chars = rest.chars();
+        let start = match chars.next() {
+            Some(']') => {
+                rest = chars.as_str();
+                break;
+            }
+            Some(c) => c,
+            None => return Err(Error::UnexpectedEnd),
+        };
+        rest = chars.as_str();
+
+        if let Some(after_dash) = rest.strip_prefix('-') {
+            let mut chars = after_dash.chars();
+            match chars.next() {
+                Some(end) if end != ']' => {
+                    if end < start {
+                        return Err(Error::UnexpectedToken);
+                    }
+                    ranges.push(CharRange { start, end });
+                    rest = chars.as_str();
+                    continue;
+                }
+                _ => {
+                    // A trailing `-` (followed by `]` or nothing) is a literal dash.
+                    ranges.push(CharRange::single(start));
+                    ranges.push(CharRange::single('-'));
+                    rest = after_dash;
+                    continue;
+                }
+            }
+        }
+
+        ranges.push(CharRange::single(start));
+    }
+
+    if ranges.is_empty() {
+        return Err(Error::UnexpectedEnd);
+    }
+
+    Ok((RegexClass { ranges, negated }, rest))
 }
 
 #[cfg(test)]
@@ -165,7 +355,7 @@ mod test {
 
     #[test]
     fn test_take_token_must_return_none_on_input_is_empty() {
-        let r = take_token("");
+        let r = take_token("").unwrap();
         assert!(r.is_none())
     }
 
@@ -173,11 +363,11 @@ mod test {
     fn test_take_token_must_return_lit_on_input_starts_with_ordinal_characters() {
         const PREFIX: &str = "abcde";
         const SUFFIX: &str = "fgh";
-        const SPECIAL_CHARS: &[char] = &['(', ')', '*', '|'];
+        const SPECIAL_CHARS: &[char] = &['(', ')', '[', '*', '+', '?', '{', '|', '.', '\\'];
 
         for c in SPECIAL_CHARS {
             let input = format!("{PREFIX}{}{SUFFIX}", c);
-            let (token, rest) = take_token(&input).unwrap();
+            let (token, rest) = take_token(&input).unwrap().unwrap();
             assert_eq!(token, Token::Literal(PREFIX.into()));
             assert_eq!(rest, &format!("{}{SUFFIX}", c));
         }
@@ -189,22 +379,215 @@ mod test {
         const SPECIALS: &[(char, Token)] = &[
             ('(', Token::LParen),
             (')', Token::RParen),
+            ('[', Token::LBracket),
             ('*', Token::Asterisk),
+            ('+', Token::Plus),
+            ('?', Token::Question),
+            ('{', Token::LBrace),
             ('|', Token::VerticalBar),
+            ('.', Token::Dot),
         ];
 
         for (c, expected) in SPECIALS {
             let input = format!("{}{SUFFIX}", c);
-            let (token, rest) = take_token(&input).unwrap();
+            let (token, rest) = take_token(&input).unwrap().unwrap();
             assert_eq!(&token, expected);
             assert_eq!(rest, &format!("{SUFFIX}"));
         }
     }
 
+    #[test]
+    fn test_take_token_must_unescape_metacharacters() {
+        const SUFFIX: &str = "fgh";
+        const ESCAPED: &[char] = &['(', ')', '*', '|', '\\', '[', '.', '+', '?', '{'];
+
+        for c in ESCAPED {
+            let input = format!("\\{c}{SUFFIX}");
+            let (token, rest) = take_token(&input).unwrap().unwrap();
+            assert_eq!(token, Token::Literal(c.to_string()));
+            assert_eq!(rest, SUFFIX);
+        }
+    }
+
+    #[test]
+    fn test_take_token_must_return_shorthand_for_escaped_class_letters() {
+        const SUFFIX: &str = "fgh";
+
+        for c in ['d', 'D', 'w', 'W', 's', 'S'] {
+            let input = format!("\\{c}{SUFFIX}");
+            let (token, rest) = take_token(&input).unwrap().unwrap();
+            assert_eq!(token, Token::Shorthand(c));
+            assert_eq!(rest, SUFFIX);
+        }
+    }
+
+    #[test]
+    fn test_take_token_fails_on_trailing_backslash() {
+        let r = take_token("\\");
+        assert_eq!(r.unwrap_err(), Error::UnexpectedEnd);
+    }
+
     #[test]
     fn test_parse_fails_if_input_contains_repeated_asterisk() {
         let r = Regex::from_str("a**");
 
         assert_eq!(r.unwrap_err(), Error::UnexpectedToken);
     }
+
+    #[test]
+    fn test_parse_class_must_parse_literal_members() {
+        let (class, rest) = parse_class("abc]xyz").unwrap();
+
+        assert_eq!(
+            class.ranges,
+            vec![
+                CharRange::single('a'),
+                CharRange::single('b'),
+                CharRange::single('c'),
+            ]
+        );
+        assert!(!class.negated);
+        assert_eq!(rest, "xyz");
+    }
+
+    #[test]
+    fn test_parse_class_must_parse_ranges() {
+        let (class, rest) = parse_class("a-z]").unwrap();
+
+        assert_eq!(class.ranges, vec![CharRange { start: 'a', end: 'z' }]);
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn test_parse_class_must_parse_negation() {
+        let (class, _) = parse_class("^a-z]").unwrap();
+
+        assert!(class.negated);
+    }
+
+    #[test]
+    fn test_parse_class_must_treat_trailing_dash_as_literal() {
+        let (class, rest) = parse_class("a-]").unwrap();
+
+        assert_eq!(
+            class.ranges,
+            vec![CharRange::single('a'), CharRange::single('-')]
+        );
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn test_parse_class_fails_if_range_is_descending() {
+        let r = parse_class("z-a]");
+
+        assert_eq!(r.unwrap_err(), Error::UnexpectedToken);
+    }
+
+    #[test]
+    fn test_parse_class_fails_if_unterminated() {
+        let r = parse_class("abc");
+
+        assert_eq!(r.unwrap_err(), Error::UnexpectedEnd);
+    }
+
+    #[test]
+    fn test_parse_dot_must_produce_a_class_node() {
+        let r = Regex::from_str(".").unwrap();
+
+        assert!(matches!(r.root, RegexNode::Class(_)));
+    }
+
+    #[test]
+    fn test_parse_bracket_expression_must_produce_a_class_node() {
+        let r = Regex::from_str("[a-z]").unwrap();
+
+        assert!(matches!(r.root, RegexNode::Class(_)));
+    }
+
+    #[test]
+    fn test_parse_plus_question_and_braces_must_produce_quantified_nodes() {
+        for pattern in ["a+", "a?", "a{2}", "a{2,}", "a{2,4}"] {
+            let r = Regex::from_str(pattern).unwrap();
+            assert!(
+                matches!(r.root, RegexNode::Quantified(_)),
+                "{pattern} should parse to a Quantified node"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_braces_must_set_min_and_max() {
+        let RegexNode::Quantified(q) = Regex::from_str("a{2,4}").unwrap().root else {
+            panic!("expected a Quantified node");
+        };
+        assert_eq!((q.min, q.max), (2, Some(4)));
+
+        let RegexNode::Quantified(q) = Regex::from_str("a{2,}").unwrap().root else {
+            panic!("expected a Quantified node");
+        };
+        assert_eq!((q.min, q.max), (2, None));
+
+        let RegexNode::Quantified(q) = Regex::from_str("a{2}").unwrap().root else {
+            panic!("expected a Quantified node");
+        };
+        assert_eq!((q.min, q.max), (2, Some(2)));
+    }
+
+    #[test]
+    fn test_parse_braces_fails_if_max_is_less_than_min() {
+        let r = Regex::from_str("a{2,1}");
+
+        assert_eq!(r.unwrap_err(), Error::UnexpectedToken);
+    }
+
+    #[test]
+    fn test_parse_braces_fails_if_empty() {
+        let r = Regex::from_str("a{}");
+
+        assert_eq!(r.unwrap_err(), Error::UnexpectedToken);
+    }
+
+    #[test]
+    fn test_parse_braces_fails_if_unterminated() {
+        let r = Regex::from_str("a{2");
+
+        assert_eq!(r.unwrap_err(), Error::UnexpectedEnd);
+    }
+
+    #[test]
+    fn test_parse_fails_if_quantifiers_are_stacked() {
+        for pattern in ["a*+", "a+*", "a?*", "a{2}{1}", "a**"] {
+            let r = Regex::from_str(pattern);
+            assert_eq!(
+                r.unwrap_err(),
+                Error::UnexpectedToken,
+                "{pattern} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_shorthand_class_must_produce_a_class_node() {
+        let r = Regex::from_str("\\d").unwrap();
+
+        assert!(matches!(r.root, RegexNode::Class(_)));
+    }
+
+    #[test]
+    fn test_escaped_asterisk_must_match_a_literal_asterisk() {
+        let nfa = crate::Nfa::from_regex("a\\*b").unwrap();
+
+        assert!(nfa.is_match("a*b"));
+        assert!(!nfa.is_match("aab"));
+    }
+
+    #[test]
+    fn test_escaped_digit_shorthand_with_quantifier_must_match_digit_runs() {
+        let nfa = crate::Nfa::from_regex("\\d+").unwrap();
+
+        assert!(nfa.is_match("0"));
+        assert!(nfa.is_match("1234567890"));
+        assert!(!nfa.is_match(""));
+        assert!(!nfa.is_match("a"));
+    }
 }